@@ -0,0 +1,178 @@
+use trie_rcv::VoteErrors;
+
+use crate::ShowErrorMessage;
+
+/// A single ballot parsed out of a BLT file: a `weight` multiplier together
+/// with the preference ordering (candidate ids, most-significant first).
+pub struct BltBallot {
+    pub weight: u64,
+    pub ranking: Vec<i32>,
+}
+
+/// The result of parsing a BLT election file. Candidate indices in the BLT
+/// grammar are 1..=`num_candidates`; they are kept as the `i32` candidate ids
+/// consumed by [`trie_rcv::RankedVote::from_vector`] without remapping.
+pub struct BltElection {
+    pub num_candidates: u32,
+    pub num_seats: u16,
+    pub ballots: Vec<BltBallot>,
+    pub candidate_names: Vec<String>,
+    pub title: String,
+}
+
+/// Errors raised while reading a BLT file. Structural problems surface as
+/// [`BltError::Malformed`]; a ballot line that is syntactically fine but does
+/// not form a valid [`trie_rcv::RankedVote`] is carried through unchanged as a
+/// [`VoteErrors`] so callers see the same messages as the hand-fed path.
+pub enum BltError {
+    Malformed(String),
+    Vote(VoteErrors),
+}
+
+impl From<VoteErrors> for BltError {
+    fn from(err: VoteErrors) -> Self {
+        BltError::Vote(err)
+    }
+}
+
+impl ShowErrorMessage for BltError {
+    fn to_error_message(&self) -> String {
+        match self {
+            BltError::Malformed(reason) => format!("Malformed BLT file: {reason}"),
+            BltError::Vote(err) => err.to_error_message(),
+        }
+    }
+}
+
+fn unquote(line: &str) -> Result<String, BltError> {
+    let trimmed = line.trim();
+    let inner = trimmed
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .ok_or_else(|| BltError::Malformed(format!("expected a quoted string, got `{trimmed}`")))?;
+    Ok(inner.to_string())
+}
+
+/// Parse the BLT election format used by tallystick and OpenTally.
+///
+/// The grammar is: a `num_candidates num_seats` header line; then ballot lines
+/// `weight pref1 pref2 ... 0` where the trailing `0` terminates the ballot and
+/// `weight` is an integer multiplier; a lone `0` ends the ballot section; then
+/// `num_candidates` quoted candidate names and a final quoted title.
+pub fn parse(content: &str) -> Result<BltElection, BltError> {
+    let mut lines = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+
+    let header = lines
+        .next()
+        .ok_or_else(|| BltError::Malformed("file is empty".to_string()))?;
+    let mut header_parts = header.split_whitespace();
+    let num_candidates: u32 = header_parts
+        .next()
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| BltError::Malformed("missing candidate count in header".to_string()))?;
+    let num_seats: u16 = header_parts
+        .next()
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| BltError::Malformed("missing seat count in header".to_string()))?;
+
+    let mut ballots: Vec<BltBallot> = Vec::new();
+    loop {
+        let line = lines.next().ok_or_else(|| {
+            BltError::Malformed("ballot section is not terminated by a `0` line".to_string())
+        })?;
+        if line == "0" {
+            break;
+        }
+
+        let mut values = line.split_whitespace();
+        let weight: u64 = values
+            .next()
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| BltError::Malformed(format!("invalid ballot weight in `{line}`")))?;
+
+        let mut ranking: Vec<i32> = Vec::new();
+        let mut terminated = false;
+        for value in values {
+            let pref: i32 = value
+                .parse()
+                .map_err(|_| BltError::Malformed(format!("invalid preference in `{line}`")))?;
+            if pref == 0 {
+                terminated = true;
+                break;
+            }
+            ranking.push(pref);
+        }
+        if !terminated {
+            return Err(BltError::Malformed(format!(
+                "ballot line `{line}` is not terminated by a `0`"
+            )));
+        }
+        ballots.push(BltBallot { weight, ranking });
+    }
+
+    // `num_candidates` comes from the untrusted header; pre-reserving on it
+    // directly would let a bogus count (e.g. `4000000000 2`) request gigabytes
+    // and abort the process. Cap the reservation at a sane bound and let the
+    // read loop below surface a genuine shortfall as BltError::Malformed.
+    let reserve = (num_candidates as usize).min(1024);
+    let mut candidate_names: Vec<String> = Vec::with_capacity(reserve);
+    for _ in 0..num_candidates {
+        let line = lines
+            .next()
+            .ok_or_else(|| BltError::Malformed("missing candidate name line".to_string()))?;
+        candidate_names.push(unquote(line)?);
+    }
+
+    let title_line = lines
+        .next()
+        .ok_or_else(|| BltError::Malformed("missing election title line".to_string()))?;
+    let title = unquote(title_line)?;
+
+    Ok(BltElection {
+        num_candidates,
+        num_seats,
+        ballots,
+        candidate_names,
+        title,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = "3 2\n\
+        4 1 2 0\n\
+        2 2 3 0\n\
+        3 3 0\n\
+        0\n\
+        \"Alice\"\n\
+        \"Bob\"\n\
+        \"Carol\"\n\
+        \"Test Election\"\n";
+
+    #[test]
+    fn parses_a_standard_blt_file() {
+        let election = match parse(FIXTURE) {
+            Ok(election) => election,
+            Err(err) => panic!("fixture should parse: {}", err.to_error_message()),
+        };
+        assert_eq!(election.num_candidates, 3);
+        assert_eq!(election.num_seats, 2);
+        assert_eq!(election.ballots.len(), 3);
+        assert_eq!(election.ballots[0].weight, 4);
+        assert_eq!(election.ballots[0].ranking, vec![1, 2]);
+        assert_eq!(election.ballots[2].ranking, vec![3]);
+        assert_eq!(election.candidate_names, vec!["Alice", "Bob", "Carol"]);
+        assert_eq!(election.title, "Test Election");
+    }
+
+    #[test]
+    fn rejects_an_unterminated_ballot_section() {
+        let result = parse("2 1\n1 2 0\n");
+        assert!(matches!(result, Err(BltError::Malformed(_))));
+    }
+}
@@ -0,0 +1,267 @@
+use crate::blt::{self, BltBallot, BltElection, BltError};
+use crate::ShowErrorMessage;
+
+/// Either a filesystem error while reading/writing a ballot file or a
+/// parse/serialization error, so both surface through the shared messaging.
+pub enum BltErrorOrIo {
+    Io(std::io::Error),
+    Blt(BltError),
+}
+
+impl From<std::io::Error> for BltErrorOrIo {
+    fn from(err: std::io::Error) -> Self {
+        BltErrorOrIo::Io(err)
+    }
+}
+
+impl From<BltError> for BltErrorOrIo {
+    fn from(err: BltError) -> Self {
+        BltErrorOrIo::Blt(err)
+    }
+}
+
+impl ShowErrorMessage for BltErrorOrIo {
+    fn to_error_message(&self) -> String {
+        match self {
+            BltErrorOrIo::Io(err) => format!("I/O error: {err}"),
+            BltErrorOrIo::Blt(err) => err.to_error_message(),
+        }
+    }
+}
+
+/// Ballot data formats understood by the conversion surface.
+pub enum BallotFormat {
+    Blt,
+    Csv,
+}
+
+impl BallotFormat {
+    /// Resolve a case-insensitive format name (`"blt"` or `"csv"`).
+    pub fn from_name(name: &str) -> Result<Self, BltError> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "blt" => Ok(BallotFormat::Blt),
+            "csv" => Ok(BallotFormat::Csv),
+            other => Err(BltError::Malformed(format!("unknown ballot format `{other}`"))),
+        }
+    }
+}
+
+/// Parse ballot data in the given format into the canonical [`BltElection`].
+pub fn read(content: &str, format: &BallotFormat) -> Result<BltElection, BltError> {
+    match format {
+        BallotFormat::Blt => blt::parse(content),
+        BallotFormat::Csv => read_csv(content),
+    }
+}
+
+/// Serialize an election into the given format.
+pub fn write(election: &BltElection, format: &BallotFormat) -> Result<String, BltError> {
+    match format {
+        BallotFormat::Blt => Ok(write_blt(election)),
+        BallotFormat::Csv => Ok(write_csv(election)),
+    }
+}
+
+/// Read the simple CSV ballot format: each row is a ranking with the most
+/// significant preference first, optionally preceded by an integer weight
+/// column. A weight column is present for every row when the first row begins
+/// with a `weight` header cell; otherwise every ballot counts once.
+///
+/// Leading `#key,value` metadata rows (written by [`write_csv`]) carry the
+/// seat count, candidate count, title and candidate names that the bare CSV
+/// grid cannot, so a `blt -> csv -> blt` round-trip preserves them. When they
+/// are absent the seat count defaults to 1 and the candidate count is derived
+/// from the highest id seen, as before.
+fn read_csv(content: &str) -> Result<BltElection, BltError> {
+    let mut rows = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .peekable();
+
+    let mut meta_seats: Option<u16> = None;
+    let mut meta_candidates: Option<u32> = None;
+    let mut meta_title: Option<String> = None;
+    let mut meta_names: Vec<String> = Vec::new();
+    while let Some(line) = rows.peek() {
+        let Some(rest) = line.strip_prefix('#') else { break };
+        let mut cells = rest.split(',').map(str::trim);
+        let key = cells.next().unwrap_or("");
+        match key {
+            "seats" => {
+                meta_seats = cells.next().and_then(|value| value.parse().ok());
+            }
+            "candidates" => {
+                meta_candidates = cells.next().and_then(|value| value.parse().ok());
+            }
+            "title" => {
+                meta_title = cells.next().map(str::to_string);
+            }
+            "names" => {
+                meta_names = cells.filter(|name| !name.is_empty()).map(str::to_string).collect();
+            }
+            _ => {}
+        }
+        rows.next();
+    }
+
+    let has_weight = rows
+        .peek()
+        .map(|line| {
+            line.split(',')
+                .next()
+                .map(|cell| cell.trim().eq_ignore_ascii_case("weight"))
+                .unwrap_or(false)
+        })
+        .unwrap_or(false);
+    if has_weight {
+        rows.next();
+    }
+
+    let mut ballots: Vec<BltBallot> = Vec::new();
+    let mut max_candidate: i32 = 0;
+    for line in rows {
+        let mut cells = line.split(',').map(str::trim).filter(|cell| !cell.is_empty());
+        let weight = if has_weight {
+            let raw = cells
+                .next()
+                .ok_or_else(|| BltError::Malformed(format!("missing weight in `{line}`")))?;
+            raw.parse::<u64>()
+                .map_err(|_| BltError::Malformed(format!("invalid weight in `{line}`")))?
+        } else {
+            1
+        };
+
+        let mut ranking: Vec<i32> = Vec::new();
+        for cell in cells {
+            let candidate = cell
+                .parse::<i32>()
+                .map_err(|_| BltError::Malformed(format!("invalid preference in `{line}`")))?;
+            max_candidate = max_candidate.max(candidate);
+            ranking.push(candidate);
+        }
+        ballots.push(BltBallot { weight, ranking });
+    }
+
+    Ok(BltElection {
+        num_candidates: meta_candidates.unwrap_or(max_candidate.max(0) as u32),
+        num_seats: meta_seats.unwrap_or(1),
+        ballots,
+        candidate_names: meta_names,
+        title: meta_title.unwrap_or_else(|| "Election".to_string()),
+    })
+}
+
+fn write_csv(election: &BltElection) -> String {
+    // carry the fields the CSV grid cannot represent as `#key,value` metadata
+    // rows so a round-trip back to BLT keeps the seat and candidate counts
+    let mut out = format!("#seats,{}\n", election.num_seats);
+    out.push_str(&format!("#candidates,{}\n", election.num_candidates));
+    out.push_str(&format!("#title,{}\n", election.title));
+    if !election.candidate_names.is_empty() {
+        out.push_str("#names");
+        for name in &election.candidate_names {
+            out.push(',');
+            out.push_str(name);
+        }
+        out.push('\n');
+    }
+    out.push_str("weight");
+    out.push('\n');
+    for ballot in &election.ballots {
+        out.push_str(&ballot.weight.to_string());
+        for pref in &ballot.ranking {
+            out.push(',');
+            out.push_str(&pref.to_string());
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn write_blt(election: &BltElection) -> String {
+    let mut out = format!("{} {}\n", election.num_candidates, election.num_seats);
+    for ballot in &election.ballots {
+        out.push_str(&ballot.weight.to_string());
+        for pref in &ballot.ranking {
+            out.push(' ');
+            out.push_str(&pref.to_string());
+        }
+        out.push_str(" 0\n");
+    }
+    out.push_str("0\n");
+
+    for index in 0..election.num_candidates as usize {
+        // fall back to a generated name when converting from a format that
+        // does not carry candidate names (e.g. CSV)
+        let name = election
+            .candidate_names
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| format!("Candidate {}", index + 1));
+        out.push_str(&format!("\"{name}\"\n"));
+    }
+    out.push_str(&format!("\"{}\"\n", election.title));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLT: &str = "3 2\n\
+        4 1 2 0\n\
+        2 2 3 0\n\
+        3 3 0\n\
+        0\n\
+        \"Alice\"\n\
+        \"Bob\"\n\
+        \"Carol\"\n\
+        \"Test Election\"\n";
+
+    fn parse_blt(content: &str) -> BltElection {
+        match blt::parse(content) {
+            Ok(election) => election,
+            Err(err) => panic!("BLT should parse: {}", err.to_error_message()),
+        }
+    }
+
+    #[test]
+    fn blt_csv_blt_round_trip_preserves_counts() {
+        let original = parse_blt(BLT);
+        let csv = write_csv(&original);
+        let recovered = match read_csv(&csv) {
+            Ok(election) => election,
+            Err(err) => panic!("CSV should parse: {}", err.to_error_message()),
+        };
+
+        // the seat and candidate counts survive the CSV hop via the metadata
+        // header rather than being reset to 1 / max-id
+        assert_eq!(recovered.num_seats, original.num_seats);
+        assert_eq!(recovered.num_candidates, original.num_candidates);
+        assert_eq!(recovered.title, original.title);
+        assert_eq!(recovered.candidate_names, original.candidate_names);
+
+        let weights: Vec<(u64, Vec<i32>)> = recovered
+            .ballots
+            .iter()
+            .map(|ballot| (ballot.weight, ballot.ranking.clone()))
+            .collect();
+        assert_eq!(
+            weights,
+            vec![(4, vec![1, 2]), (2, vec![2, 3]), (3, vec![3])]
+        );
+    }
+
+    #[test]
+    fn headerless_csv_falls_back_to_derived_counts() {
+        let election = match read_csv("weight\n2,1,3\n1,2\n") {
+            Ok(election) => election,
+            Err(err) => panic!("CSV should parse: {}", err.to_error_message()),
+        };
+        assert_eq!(election.num_seats, 1);
+        assert_eq!(election.num_candidates, 3);
+        assert_eq!(election.ballots[0].weight, 2);
+        assert_eq!(election.ballots[0].ranking, vec![1, 3]);
+    }
+}
@@ -1,9 +1,13 @@
 use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::{PyTuple};
+use pyo3::types::{PyDict, PyList, PyTuple};
+use pyo3::wrap_pyfunction;
 use pyo3_stub_gen::{
     derive::gen_stub_pymethods, derive::gen_stub_pyclass,
+    derive::gen_stub_pyclass_enum, derive::gen_stub_pyfunction,
     define_stub_info_gatherer
 };
 
@@ -14,7 +18,40 @@ use trie_rcv::{
 
 const WITHOLD_VOTE_VAL: i32 = SpecialVotes::WITHHOLD.to_int();
 
-trait ShowErrorMessage {
+mod blt;
+mod convert;
+mod numeric;
+mod stv;
+mod tiebreak;
+
+use numeric::NumericMode;
+
+/// Python-visible wrapper over [`trie_rcv::EliminationStrategies`] so callers can
+/// choose how candidates are dropped during a single-winner count.
+#[gen_stub_pyclass_enum]
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EliminationStrategy {
+    EliminateAll,
+    DowdallScoring,
+    RankedPairs,
+    CondorcetRankedPairs,
+}
+
+impl EliminationStrategy {
+    fn to_trie(self) -> EliminationStrategies {
+        match self {
+            EliminationStrategy::EliminateAll => EliminationStrategies::EliminateAll,
+            EliminationStrategy::DowdallScoring => EliminationStrategies::DowdallScoring,
+            EliminationStrategy::RankedPairs => EliminationStrategies::RankedPairs,
+            EliminationStrategy::CondorcetRankedPairs => {
+                EliminationStrategies::CondorcetRankedPairs
+            }
+        }
+    }
+}
+
+pub(crate) trait ShowErrorMessage {
     fn to_error_message(&self) -> String;
 }
 
@@ -38,28 +75,99 @@ impl ShowErrorMessage for VoteErrors {
 #[pyclass]
 pub struct VotesAggregator {
     raw_votes_cache: HashMap<u64, Vec<i32>>,
+    // identical ballots are collapsed into a single entry with a running
+    // count, so a ballot cast 10,000 times is stored once rather than as
+    // 10,000 separate vectors; the count is expanded on flush
+    weighted_votes_cache: HashMap<Vec<i32>, u64>,
+    // numeric backend used for score and surplus-transfer accumulation
+    numeric_mode: NumericMode,
+    // optional seed driving deterministic SHA-based tie-breaks in the
+    // transfer-based counts (determine_winners / *_with_trace); the legacy
+    // single-winner determine_winner breaks ties inside the trie and is not
+    // influenced by this seed
+    tie_break_seed: Option<String>,
     rcv: RankedChoiceVoteTrie
 }
 impl VotesAggregator {
     fn _flush_votes(&mut self) -> Result<bool, VoteErrors> {
         // convert raw votes into RankedVotes into the trie
         let mut raw_votes_inserted = false;
-        for (_, raw_vote) in &self.raw_votes_cache {
+        for raw_vote in self.raw_votes_cache.values() {
             let cast_result = RankedVote::from_vector(raw_vote)?;
             self.rcv.insert_vote(cast_result);
             raw_votes_inserted = true
         }
         self.raw_votes_cache.clear();
+
+        // expand each collapsed ballot back into `weight` trie insertions
+        for (ranking, weight) in &self.weighted_votes_cache {
+            for _ in 0..*weight {
+                self.rcv.insert_vote(RankedVote::from_vector(ranking)?);
+            }
+            raw_votes_inserted = true
+        }
+        self.weighted_votes_cache.clear();
+
         Ok(raw_votes_inserted)
     }
+
+    fn _materialize_ballots(&self) -> PyResult<Vec<stv::WeightedBallot>> {
+        // gather the ballots currently held in the caches as weighted ballots
+        // for the transfer-based counts, validating each ranking on the way
+        let mut ballots: Vec<stv::WeightedBallot> = Vec::new();
+        for ranking in self.raw_votes_cache.values() {
+            RankedVote::from_vector(ranking)
+                .map_err(|err| PyValueError::new_err(err.to_string()))?;
+            ballots.push(stv::WeightedBallot { ranking: ranking.clone(), weight: 1 });
+        }
+        for (ranking, weight) in &self.weighted_votes_cache {
+            RankedVote::from_vector(ranking)
+                .map_err(|err| PyValueError::new_err(err.to_string()))?;
+            ballots.push(stv::WeightedBallot { ranking: ranking.clone(), weight: *weight });
+        }
+        // the transfer-based counts read ballot rankings, which only the caches
+        // retain; once ballots have been flushed into the trie (by flush_votes
+        // or determine_winner) the rankings are gone, so refuse rather than
+        // silently count zero ballots
+        if ballots.is_empty() && self.rcv.get_num_votes() > 0 {
+            return Err(PyValueError::new_err(
+                "ballots have already been flushed into the trie; run the STV \
+                 count before flush_votes()/determine_winner()"
+            ));
+        }
+        Ok(ballots)
+    }
+
+    fn _load_blt(&mut self, content: &str) -> Result<u16, blt::BltError> {
+        let election = blt::parse(content)?;
+        for ballot in &election.ballots {
+            // validate each line before collapsing it into the weight map so
+            // malformed ballots still surface through the VoteErrors path
+            RankedVote::from_vector(&ballot.ranking)?;
+            *self
+                .weighted_votes_cache
+                .entry(ballot.ranking.clone())
+                .or_insert(0) += ballot.weight;
+        }
+        Ok(election.num_seats)
+    }
 }
 #[gen_stub_pymethods]
 #[pymethods]
 impl VotesAggregator {
+    /// `tie_break_seed`, when given, makes ties reproducible in the
+    /// transfer-based counts (determine_winners / *_with_trace) via a SHA-based
+    /// ordering. It does NOT affect the legacy single-winner determine_winner,
+    /// which resolves ties inside the trie.
     #[new]
-    fn new() -> Self {
+    #[pyo3(signature = (numeric_mode = NumericMode::Float, tie_break_seed = None))]
+    fn new(numeric_mode: NumericMode, tie_break_seed: Option<String>) -> Self {
         VotesAggregator {
-            raw_votes_cache: Default::default(), rcv: Default::default()
+            raw_votes_cache: Default::default(),
+            weighted_votes_cache: Default::default(),
+            numeric_mode,
+            tie_break_seed,
+            rcv: Default::default()
         }
     }
 
@@ -74,7 +182,8 @@ impl VotesAggregator {
         // return the total number of votes cast
         Ok(
             self.rcv.get_num_votes() +
-            self.raw_votes_cache.len() as u64
+            self.raw_votes_cache.len() as u64 +
+            self.weighted_votes_cache.values().sum::<u64>()
         )
     }
 
@@ -87,48 +196,198 @@ impl VotesAggregator {
             Ok(_) => "".to_string(),
             Err(err) => err.to_error_message()
         };
-        Python::with_gil(|py| {
-            let elements: Vec<PyObject> = vec![
-                cast_successful.into_py(py),
-                error_message.into_py(py)
-            ];
-            Ok(PyTuple::new_bound(py, elements).into())
+        Python::attach(|py| {
+            let tuple = (cast_successful, error_message).into_pyobject(py)?;
+            Ok(tuple.unbind())
         })
     }
 
+    fn load_blt(&mut self, path_or_content: &str) -> PyResult<u16> {
+        // ingest a BLT election file (either a filesystem path or the file
+        // contents themselves) directly into the trie, expanding each weighted
+        // ballot line into the number of trie insertions it represents
+        let content = match fs::read_to_string(Path::new(path_or_content)) {
+            Ok(file_content) => file_content,
+            Err(_) => path_or_content.to_string(),
+        };
+        match self._load_blt(&content) {
+            Ok(num_seats) => Ok(num_seats),
+            Err(err) => Err(PyValueError::new_err(err.to_error_message())),
+        }
+    }
+
+    #[staticmethod]
+    fn from_blt(path_or_content: &str) -> PyResult<Self> {
+        let mut aggregator = VotesAggregator::new(NumericMode::Float, None);
+        aggregator.load_blt(path_or_content)?;
+        Ok(aggregator)
+    }
+
     fn insert_vote_ranking(&mut self, vote_id: u64, vote_ranking: i32) {
         let vote = self.raw_votes_cache.entry(vote_id).or_insert(vec![]);
         vote.push(vote_ranking)
     }
 
+    fn insert_vote_ranking_weighted(
+        &mut self, vote_id: u64, vote_ranking: Vec<i32>, weight: u64
+    ) -> PyResult<()> {
+        // collapse an already-complete ballot into the weight map; a ballot
+        // submitted `weight` times is stored once with that weight. Flushing
+        // into the trie still performs `weight` separate insertions; the
+        // transfer-based counts read the weight directly. Ballots are keyed by
+        // their ranking (identical ballots share a count), so `vote_id` is
+        // accepted for signature parity with insert_vote_ranking but is not
+        // used to distinguish ballots.
+        let _ = vote_id;
+        match RankedVote::from_vector(&vote_ranking) {
+            Ok(_) => {
+                *self.weighted_votes_cache.entry(vote_ranking).or_insert(0) += weight;
+                Ok(())
+            }
+            Err(err) => Err(PyValueError::new_err(err.to_string()))
+        }
+    }
+
     fn insert_empty_votes(&mut self, num_votes: u64) -> PyResult<bool> {
         // insert withhold votes to represent registered voters
-        // who did not vote in the poll
-        for _ in 0..num_votes {
-            let withhold_vote: RankedVote = RankedVote::from_vector(
-                &vec![WITHOLD_VOTE_VAL]
-            ).unwrap();
-
-            self.rcv.insert_vote(withhold_vote)
-        }
+        // who did not vote in the poll; these are all identical, so collapse
+        // them into a single weighted entry (expanded into `num_votes` trie
+        // insertions on flush)
+        *self.weighted_votes_cache
+            .entry(vec![WITHOLD_VOTE_VAL])
+            .or_insert(0) += num_votes;
         Ok(true)
     }
 
-    fn determine_winner(&mut self) -> PyResult<Option<u16>> {
-        // TODO: implement elimination strategy selection
-        self.rcv.set_elimination_strategy(EliminationStrategies::DowdallScoring);
-        let flush_result = self._flush_votes();
-        if flush_result.is_err() {
-            return Err(PyValueError::new_err(flush_result.unwrap_err().to_string()))
+    #[pyo3(signature = (strategy = EliminationStrategy::DowdallScoring))]
+    fn determine_winner(&mut self, strategy: EliminationStrategy) -> PyResult<Option<u16>> {
+        // This count runs inside the trie, whose Dowdall scoring is fixed to
+        // f64, so it does not use the CountValue backend. Exact arithmetic
+        // reaches only the transfer-based counts (determine_winners and the
+        // *_with_trace methods); here the exact modes are rejected rather than
+        // silently returning float behaviour.
+        if self.numeric_mode != NumericMode::Float {
+            return Err(PyValueError::new_err(
+                "determine_winner only supports NumericMode.Float; use \
+                 determine_winners() for fixed/rational exact counts"
+            ));
+        }
+        // The trie resolves elimination ties internally and cannot take the
+        // seeded SHA tie-break, so a seed supplied for the transfer-based counts
+        // would be silently ignored here. Surface that rather than letting a
+        // caller believe single-winner ties are reproducibly broken.
+        if self.tie_break_seed.is_some() {
+            return Err(PyValueError::new_err(
+                "tie_break_seed does not apply to determine_winner (the trie \
+                 breaks ties internally); use determine_winners()/\
+                 determine_winners_with_trace() for seeded tie-breaks"
+            ));
+        }
+        self.rcv.set_elimination_strategy(strategy.to_trie());
+        if let Err(err) = self._flush_votes() {
+            return Err(PyValueError::new_err(err.to_string()))
         }
         let winner = self.rcv.determine_winner();
         Ok(winner)
     }
+
+    fn determine_winners(&mut self, num_seats: u16) -> PyResult<Vec<u16>> {
+        // run single transferable vote over the cached ballots and return the
+        // elected candidates in the order they reached the quota
+        let ballots = self._materialize_ballots()?;
+        let winners = stv::determine_winners(
+            ballots, num_seats, self.numeric_mode, self.tie_break_seed.as_deref()
+        );
+        Ok(winners.into_iter().map(|candidate| candidate as u16).collect())
+    }
+
+    fn determine_winner_with_trace(&mut self) -> PyResult<Py<PyDict>> {
+        // audit a single-seat STV count. The trace and the `stv_winner` it
+        // reports both come from one STV run for a single seat, so the log
+        // always matches that engine's decision and agrees with
+        // determine_winners(1). This is NOT an audit of the trie-based
+        // determine_winner(): the trie uses Dowdall/Condorcet elimination, which
+        // can pick a different winner, so the result is deliberately keyed as
+        // `stv_winner` (and tagged `engine = "stv"`) rather than presented as
+        // the authoritative single-winner result.
+        let ballots = self._materialize_ballots()?;
+        let outcome = stv::run(ballots, 1, self.numeric_mode, self.tie_break_seed.as_deref());
+        Python::attach(|py| outcome_to_dict(py, &outcome, false))
+    }
+
+    fn determine_winners_with_trace(&mut self, num_seats: u16) -> PyResult<Py<PyDict>> {
+        // the multi-winner counterpart: same STV engine as determine_winners,
+        // so the trace matches the elected seats it returns
+        let ballots = self._materialize_ballots()?;
+        let outcome = stv::run(
+            ballots, num_seats, self.numeric_mode, self.tie_break_seed.as_deref()
+        );
+        Python::attach(|py| outcome_to_dict(py, &outcome, true))
+    }
+}
+
+/// Render an [`stv::StvOutcome`] as a Python dict: a `rounds` list of per-round
+/// count sheets, an `engine` tag naming the count that produced them, plus the
+/// result (`stv_winner` for a single seat, `winners` for a multi-seat count).
+/// The single-seat key is `stv_winner`, not `winner`, so it is never mistaken
+/// for the authoritative trie-based `determine_winner()` result.
+fn outcome_to_dict(
+    py: Python<'_>, outcome: &stv::StvOutcome, multi: bool
+) -> PyResult<Py<PyDict>> {
+    let rounds = PyList::empty(py);
+    for record in &outcome.rounds {
+        let round = PyDict::new(py);
+        round.set_item("round", record.round)?;
+        round.set_item("continuing", record.continuing.clone())?;
+        let tallies = PyDict::new(py);
+        for (candidate, tally) in &record.tallies {
+            tallies.set_item(candidate, tally)?;
+        }
+        round.set_item("tallies", tallies)?;
+        round.set_item("elected", record.elected.clone())?;
+        round.set_item("eliminated", record.eliminated)?;
+        round.set_item("transferred", record.transferred)?;
+        rounds.append(round)?;
+    }
+    let result = PyDict::new(py);
+    result.set_item("engine", "stv")?;
+    if multi {
+        let winners: Vec<u16> = outcome.winners.iter().map(|&c| c as u16).collect();
+        result.set_item("winners", winners)?;
+    } else {
+        result.set_item("stv_winner", outcome.winners.first().map(|&c| c as u16))?;
+    }
+    result.set_item("rounds", rounds)?;
+    Ok(result.unbind())
+}
+
+/// Convert ballot data between supported formats (`blt` and `csv`) without
+/// running a count, reading `infile` and writing `outfile`. Parsing and
+/// serialization failures surface through the same messaging as the rest of the
+/// crate.
+#[gen_stub_pyfunction]
+#[pyfunction]
+fn convert_ballots(
+    infile: &str, outfile: &str, in_format: &str, out_format: &str
+) -> PyResult<()> {
+    let result = (|| -> Result<(), convert::BltErrorOrIo> {
+        let source = std::fs::read_to_string(infile)?;
+        let in_format = convert::BallotFormat::from_name(in_format)?;
+        let out_format = convert::BallotFormat::from_name(out_format)?;
+        let election = convert::read(&source, &in_format)?;
+        let serialized = convert::write(&election, &out_format)?;
+        std::fs::write(outfile, serialized)?;
+        Ok(())
+    })();
+    result.map_err(|err| PyValueError::new_err(err.to_error_message()))
 }
 
 #[pymodule]
 fn ranked_choice_vote(module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add_class::<VotesAggregator>()?;
+    module.add_class::<NumericMode>()?;
+    module.add_class::<EliminationStrategy>()?;
+    module.add_function(wrap_pyfunction!(convert_ballots, module)?)?;
     Ok(())
 }
 
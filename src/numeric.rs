@@ -0,0 +1,170 @@
+use std::cmp::Ordering;
+
+use pyo3::prelude::*;
+use pyo3_stub_gen::derive::gen_stub_pyclass_enum;
+
+/// Numeric backend used for score and surplus-transfer accumulation. Floating
+/// point loses precision on fractional transfer values and makes close counts
+/// non-reproducible across platforms. The rational mode keeps counts exact; the
+/// fixed mode keeps them in integer arithmetic to six decimal places, truncating
+/// transfer values beyond that. Both give deterministic, auditable results.
+#[gen_stub_pyclass_enum]
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum NumericMode {
+    /// Fixed-point arithmetic scaled by [`FIXED_SCALE`] (six guard digits).
+    Fixed,
+    /// IEEE-754 double precision (the historical behaviour).
+    Float,
+    /// Exact `i128/i128` reduced fractions.
+    Rational,
+}
+
+/// Scale factor of [`NumericMode::Fixed`]: 10^6, i.e. six decimal guard digits.
+const FIXED_SCALE: i128 = 1_000_000;
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a.max(1)
+}
+
+/// A count value carrying its arithmetic mode. All operations assume both
+/// operands share the same mode, which is guaranteed by constructing every
+/// value in a count through [`CountValue::from_int`] with a single mode.
+#[derive(Clone, Copy)]
+pub enum CountValue {
+    Fixed(i128),
+    Float(f64),
+    // numerator / denominator, kept reduced with a positive denominator
+    Rational(i128, i128),
+}
+
+impl CountValue {
+    pub fn from_int(mode: NumericMode, value: u64) -> Self {
+        match mode {
+            NumericMode::Fixed => CountValue::Fixed(value as i128 * FIXED_SCALE),
+            NumericMode::Float => CountValue::Float(value as f64),
+            NumericMode::Rational => CountValue::Rational(value as i128, 1),
+        }
+    }
+
+    pub fn zero(mode: NumericMode) -> Self {
+        CountValue::from_int(mode, 0)
+    }
+
+    fn reduce(num: i128, den: i128) -> Self {
+        let sign = if den < 0 { -1 } else { 1 };
+        let divisor = gcd(num, den);
+        CountValue::Rational(sign * num / divisor, sign * den / divisor)
+    }
+
+    pub fn add(self, other: CountValue) -> CountValue {
+        match (self, other) {
+            (CountValue::Fixed(a), CountValue::Fixed(b)) => CountValue::Fixed(a + b),
+            (CountValue::Float(a), CountValue::Float(b)) => CountValue::Float(a + b),
+            (CountValue::Rational(an, ad), CountValue::Rational(bn, bd)) => {
+                CountValue::reduce(an * bd + bn * ad, ad * bd)
+            }
+            _ => unreachable!("count values must share a numeric mode"),
+        }
+    }
+
+    pub fn sub(self, other: CountValue) -> CountValue {
+        match (self, other) {
+            (CountValue::Fixed(a), CountValue::Fixed(b)) => CountValue::Fixed(a - b),
+            (CountValue::Float(a), CountValue::Float(b)) => CountValue::Float(a - b),
+            (CountValue::Rational(an, ad), CountValue::Rational(bn, bd)) => {
+                CountValue::reduce(an * bd - bn * ad, ad * bd)
+            }
+            _ => unreachable!("count values must share a numeric mode"),
+        }
+    }
+
+    /// Multiply by a ratio `num / den` (the surplus transfer value). The result
+    /// is exact in the rational mode; the fixed mode truncates it to its six
+    /// guard digits.
+    pub fn scale(self, num: CountValue, den: CountValue) -> CountValue {
+        match (self, num, den) {
+            (CountValue::Fixed(v), CountValue::Fixed(n), CountValue::Fixed(d)) => {
+                if d == 0 { CountValue::Fixed(0) } else { CountValue::Fixed(v * n / d) }
+            }
+            (CountValue::Float(v), CountValue::Float(n), CountValue::Float(d)) => {
+                CountValue::Float(if d == 0.0 { 0.0 } else { v * n / d })
+            }
+            (
+                CountValue::Rational(vn, vd),
+                CountValue::Rational(nn, nd),
+                CountValue::Rational(dn, dd),
+            ) => {
+                if dn == 0 {
+                    CountValue::Rational(0, 1)
+                } else {
+                    // v * (n / d) = (vn*nn*dd) / (vd*nd*dn)
+                    CountValue::reduce(vn * nn * dd, vd * nd * dn)
+                }
+            }
+            _ => unreachable!("count values must share a numeric mode"),
+        }
+    }
+
+    pub fn cmp(&self, other: &CountValue) -> Ordering {
+        match (self, other) {
+            (CountValue::Fixed(a), CountValue::Fixed(b)) => a.cmp(b),
+            (CountValue::Float(a), CountValue::Float(b)) => {
+                a.partial_cmp(b).unwrap_or(Ordering::Equal)
+            }
+            (CountValue::Rational(an, ad), CountValue::Rational(bn, bd)) => {
+                (an * bd).cmp(&(bn * ad))
+            }
+            _ => unreachable!("count values must share a numeric mode"),
+        }
+    }
+
+    /// Lossy conversion to `f64` for reporting tallies back to Python.
+    pub fn to_f64(self) -> f64 {
+        match self {
+            CountValue::Fixed(v) => v as f64 / FIXED_SCALE as f64,
+            CountValue::Float(v) => v,
+            CountValue::Rational(n, d) => n as f64 / d as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rational_arithmetic_is_exact() {
+        // 1/10 + 2/10 == 3/10 exactly, where the equivalent float sum would not
+        let tenth = CountValue::from_int(NumericMode::Rational, 1)
+            .scale(CountValue::from_int(NumericMode::Rational, 1),
+                   CountValue::from_int(NumericMode::Rational, 10));
+        let fifth = CountValue::from_int(NumericMode::Rational, 2)
+            .scale(CountValue::from_int(NumericMode::Rational, 1),
+                   CountValue::from_int(NumericMode::Rational, 10));
+        let sum = tenth.add(fifth);
+        let three_tenths = CountValue::from_int(NumericMode::Rational, 3)
+            .scale(CountValue::from_int(NumericMode::Rational, 1),
+                   CountValue::from_int(NumericMode::Rational, 10));
+        assert_eq!(sum.cmp(&three_tenths), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn surplus_transfer_value_round_trips() {
+        // a tally of 10 with a surplus of 3 transfers each ballot at 3/10; the
+        // whole pile therefore carries exactly the surplus back
+        for mode in [NumericMode::Fixed, NumericMode::Float, NumericMode::Rational] {
+            let tally = CountValue::from_int(mode, 10);
+            let surplus = tally.sub(CountValue::from_int(mode, 7));
+            let transferred = tally.scale(surplus, tally);
+            assert_eq!(transferred.cmp(&surplus), std::cmp::Ordering::Equal);
+            assert_eq!(transferred.to_f64(), 3.0);
+        }
+    }
+}
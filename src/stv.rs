@@ -0,0 +1,276 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::numeric::{CountValue, NumericMode};
+use crate::tiebreak;
+
+/// A ballot fed into the STV count: a preference ordering (candidate ids,
+/// most-significant first) together with the number of identical ballots it
+/// stands for. Non-positive entries (special/withhold votes) are treated as
+/// the end of the usable preference list.
+pub struct WeightedBallot {
+    pub ranking: Vec<i32>,
+    pub weight: u64,
+}
+
+struct CountingBallot {
+    ranking: Vec<i32>,
+    value: CountValue,
+    // index of the next preference to consider in `ranking`
+    cursor: usize,
+}
+
+impl CountingBallot {
+    /// Advance the cursor to the next preference that is still a continuing
+    /// candidate, returning it (or `None` once the ballot is exhausted).
+    fn current(&mut self, continuing: &HashSet<i32>) -> Option<i32> {
+        while let Some(&pref) = self.ranking.get(self.cursor) {
+            if pref <= 0 {
+                // a special/withhold vote exhausts the ballot
+                self.cursor = self.ranking.len();
+                return None;
+            }
+            if continuing.contains(&pref) {
+                return Some(pref);
+            }
+            self.cursor += 1;
+        }
+        None
+    }
+}
+
+/// The count sheet for a single round: who was still in the running, each
+/// continuing candidate's tally that round, who was elected or eliminated, and
+/// the total ballot weight transferred out of them.
+pub struct RoundRecord {
+    pub round: usize,
+    pub continuing: Vec<i32>,
+    pub tallies: Vec<(i32, f64)>,
+    pub elected: Vec<i32>,
+    pub eliminated: Option<i32>,
+    pub transferred: f64,
+}
+
+/// The full result of a count: the winners in election order plus the
+/// round-by-round trace that produced them.
+pub struct StvOutcome {
+    pub winners: Vec<i32>,
+    pub rounds: Vec<RoundRecord>,
+}
+
+/// Run single transferable vote over `ballots` for `num_seats` seats and return
+/// the elected candidate ids (in election order) together with the per-round
+/// count trace.
+///
+/// The Droop quota `q = floor(total_valid / (num_seats + 1)) + 1` is used;
+/// candidates reaching the quota are elected and their surplus is redistributed
+/// to the next available preference at transfer value `surplus / tally`. When no
+/// candidate reaches the quota the lowest-tallied candidate is eliminated and
+/// their ballots transfer at full value. All score and transfer accumulation
+/// runs through the [`CountValue`] backend selected by `mode`. When several
+/// candidates are tied for elimination, `seed` (if set) drives a deterministic
+/// SHA-based tie-break; otherwise the lowest candidate id is eliminated.
+pub fn run(
+    ballots: Vec<WeightedBallot>, num_seats: u16, mode: NumericMode, seed: Option<&str>
+) -> StvOutcome {
+    let mut continuing: HashSet<i32> = HashSet::new();
+    let mut counting: Vec<CountingBallot> = Vec::new();
+    let mut total_valid: u64 = 0;
+
+    for ballot in ballots {
+        let has_candidate = ballot.ranking.iter().any(|&pref| pref > 0);
+        for &pref in &ballot.ranking {
+            if pref > 0 {
+                continuing.insert(pref);
+            }
+        }
+        if has_candidate {
+            total_valid += ballot.weight;
+            counting.push(CountingBallot {
+                ranking: ballot.ranking,
+                value: CountValue::from_int(mode, ballot.weight),
+                cursor: 0,
+            });
+        }
+    }
+
+    let num_seats = num_seats as usize;
+    let quota = CountValue::from_int(mode, total_valid / (num_seats as u64 + 1) + 1);
+    let mut elected: Vec<i32> = Vec::new();
+    let mut rounds: Vec<RoundRecord> = Vec::new();
+    let mut round: usize = 0;
+
+    while elected.len() < num_seats && !continuing.is_empty() {
+        round += 1;
+        // elect every remaining continuing candidate once the seats left to
+        // fill equal the number still in the running
+        if continuing.len() <= num_seats - elected.len() {
+            let mut remaining: Vec<i32> = continuing.iter().copied().collect();
+            remaining.sort_unstable();
+            rounds.push(RoundRecord {
+                round,
+                continuing: remaining.clone(),
+                tallies: Vec::new(),
+                elected: remaining.clone(),
+                eliminated: None,
+                transferred: 0.0,
+            });
+            elected.extend(remaining);
+            break;
+        }
+
+        let mut tallies: HashMap<i32, CountValue> =
+            continuing.iter().map(|&c| (c, CountValue::zero(mode))).collect();
+        for ballot in counting.iter_mut() {
+            if let Some(candidate) = ballot.current(&continuing) {
+                let entry = tallies.get_mut(&candidate).unwrap();
+                *entry = entry.add(ballot.value);
+            }
+        }
+
+        let mut continuing_snapshot: Vec<i32> = continuing.iter().copied().collect();
+        continuing_snapshot.sort_unstable();
+        let mut tally_snapshot: Vec<(i32, f64)> =
+            tallies.iter().map(|(&c, t)| (c, t.to_f64())).collect();
+        tally_snapshot.sort_by_key(|entry| entry.0);
+
+        // candidates that have reached quota, highest tally first
+        let mut winners_this_round: Vec<(i32, CountValue)> = tallies
+            .iter()
+            .filter(|&(_, tally)| tally.cmp(&quota) != std::cmp::Ordering::Less)
+            .map(|(&candidate, &tally)| (candidate, tally))
+            .collect();
+        winners_this_round.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        if let Some(&(candidate, tally)) = winners_this_round.first() {
+            // elect a single candidate per round and recompute tallies before
+            // the next election, so that a surplus transferred onto another
+            // candidate who also reached quota this round is counted against a
+            // fresh tally rather than double-transferred at a stale value
+            let surplus = tally.sub(quota);
+            continuing.remove(&candidate);
+            elected.push(candidate);
+            // scale down every ballot currently sitting on this candidate
+            // at transfer value surplus/tally and move it to its next pref
+            for ballot in counting.iter_mut() {
+                if ballot.ranking.get(ballot.cursor) == Some(&candidate) {
+                    ballot.value = ballot.value.scale(surplus, tally);
+                    ballot.cursor += 1;
+                }
+            }
+            rounds.push(RoundRecord {
+                round,
+                continuing: continuing_snapshot,
+                tallies: tally_snapshot,
+                elected: vec![candidate],
+                eliminated: None,
+                transferred: surplus.to_f64(),
+            });
+            continue;
+        }
+
+        // nobody reached quota: eliminate the weakest candidate and transfer
+        // their ballots at full value, breaking ties deterministically
+        let loser = lowest_tallied(&tallies, seed, round);
+        let transferred = loser
+            .and_then(|candidate| tallies.get(&candidate))
+            .map(|tally| tally.to_f64())
+            .unwrap_or(0.0);
+        match loser {
+            Some(candidate) => {
+                continuing.remove(&candidate);
+                for ballot in counting.iter_mut() {
+                    if ballot.ranking.get(ballot.cursor) == Some(&candidate) {
+                        ballot.cursor += 1;
+                    }
+                }
+                rounds.push(RoundRecord {
+                    round,
+                    continuing: continuing_snapshot,
+                    tallies: tally_snapshot,
+                    elected: Vec::new(),
+                    eliminated: Some(candidate),
+                    transferred,
+                });
+            }
+            None => break,
+        }
+    }
+
+    StvOutcome { winners: elected, rounds }
+}
+
+/// Convenience wrapper returning only the winners in election order.
+pub fn determine_winners(
+    ballots: Vec<WeightedBallot>, num_seats: u16, mode: NumericMode, seed: Option<&str>
+) -> Vec<i32> {
+    run(ballots, num_seats, mode, seed).winners
+}
+
+/// Pick the candidate to eliminate: the lowest tally, with ties broken by the
+/// seeded SHA ordering when a seed is given and by ascending id otherwise.
+fn lowest_tallied(
+    tallies: &HashMap<i32, CountValue>, seed: Option<&str>, round: usize
+) -> Option<i32> {
+    let min_value = tallies
+        .values()
+        .min_by(|a, b| a.cmp(b))
+        .copied()?;
+    let mut tied: Vec<i32> = tallies
+        .iter()
+        .filter(|&(_, value)| value.cmp(&min_value) == std::cmp::Ordering::Equal)
+        .map(|(&candidate, _)| candidate)
+        .collect();
+    tied.sort_unstable();
+    match seed {
+        Some(seed) if tied.len() > 1 => tiebreak::order_tied(seed, round, &tied).first().copied(),
+        _ => tied.first().copied(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numeric::NumericMode;
+
+    fn ballot(ranking: &[i32], weight: u64) -> WeightedBallot {
+        WeightedBallot { ranking: ranking.to_vec(), weight }
+    }
+
+    #[test]
+    fn two_seat_count_elects_expected_candidates() {
+        // 10 ballots, Droop quota floor(10/3)+1 = 4. Candidate 1 reaches quota
+        // on first preferences and is elected; candidate 2 is then eliminated as
+        // the weakest and candidate 3 fills the final seat.
+        let ballots = vec![
+            ballot(&[1], 6),
+            ballot(&[2], 1),
+            ballot(&[3], 3),
+        ];
+        let winners = determine_winners(ballots, 2, NumericMode::Rational, None);
+        assert_eq!(winners, vec![1, 3]);
+    }
+
+    #[test]
+    fn numeric_mode_does_not_change_the_winners() {
+        // the exact and float backends must agree on the outcome for a count
+        // whose transfer values are representable either way
+        let make = || vec![ballot(&[1, 3], 6), ballot(&[2, 3], 1), ballot(&[3], 3)];
+        let rational = determine_winners(make(), 2, NumericMode::Rational, None);
+        let float = determine_winners(make(), 2, NumericMode::Float, None);
+        let fixed = determine_winners(make(), 2, NumericMode::Fixed, None);
+        assert_eq!(rational, float);
+        assert_eq!(rational, fixed);
+    }
+
+    #[test]
+    fn single_seat_majority_winner() {
+        let ballots = vec![
+            ballot(&[1], 6),
+            ballot(&[2], 1),
+            ballot(&[3], 3),
+        ];
+        let outcome = run(ballots, 1, NumericMode::Rational, None);
+        assert_eq!(outcome.winners, vec![1]);
+        assert!(!outcome.rounds.is_empty());
+    }
+}
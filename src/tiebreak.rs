@@ -0,0 +1,24 @@
+use sha2::{Digest, Sha256};
+
+/// Deterministically order candidates that are tied (e.g. for elimination).
+///
+/// Mirrors OpenTally's SHA-based pseudo-random tie-break: each tied candidate is
+/// hashed as `seed || round || candidate_id` with SHA-256 and the candidates are
+/// ordered by the resulting digest, so re-running a count with the same seed
+/// always breaks the tie identically. The candidate returned first is the one
+/// selected (for elimination). With no seed the caller should fall back to a
+/// plain ascending-id order instead of calling this.
+pub fn order_tied(seed: &str, round: usize, candidates: &[i32]) -> Vec<i32> {
+    let mut ordered: Vec<([u8; 32], i32)> = candidates
+        .iter()
+        .map(|&candidate| {
+            let mut hasher = Sha256::new();
+            hasher.update(seed.as_bytes());
+            hasher.update(round.to_le_bytes());
+            hasher.update(candidate.to_le_bytes());
+            (hasher.finalize().into(), candidate)
+        })
+        .collect();
+    ordered.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+    ordered.into_iter().map(|(_, candidate)| candidate).collect()
+}